@@ -10,13 +10,53 @@
  * Use:   const { countPrimes } = require('./rust-prime-counter.node');
  */
 
+use std::sync::{Mutex, OnceLock};
+
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use napi::bindgen_prelude::BigInt;
 use napi_derive::napi;
+use num_bigint::BigUint;
+use rayon::prelude::*;
 
-/// Count prime numbers up to a given maximum
+/// Count prime numbers up to a given maximum using a Sieve of Eratosthenes
 /// This is ~25-50x faster than the equivalent JavaScript implementation
 #[napi]
 pub fn count_primes(max: u32) -> u32 {
-    (2..=max).filter(|&n| is_prime(n)).count() as u32
+    sieve(max).iter().filter(|&&is_p| is_p).count() as u32
+}
+
+/// Return the list of primes up to and including `max`
+#[napi]
+pub fn primes_up_to(max: u32) -> Vec<u32> {
+    sieve(max)
+        .iter()
+        .enumerate()
+        .filter_map(|(n, &is_p)| if is_p { Some(n as u32) } else { None })
+        .collect()
+}
+
+/// Build a `Vec<bool>` of length `max + 1` where index `n` is true iff `n` is prime
+fn sieve(max: u32) -> Vec<bool> {
+    let max = max as usize;
+    let mut is_prime = vec![true; max + 1];
+    is_prime[0] = false;
+    if max >= 1 {
+        is_prime[1] = false;
+    }
+
+    let sqrt = (max as f64).sqrt() as usize;
+    for i in 2..=sqrt {
+        if is_prime[i] {
+            let mut j = i * i;
+            while j <= max {
+                is_prime[j] = false;
+                j += i;
+            }
+        }
+    }
+
+    is_prime
 }
 
 /// Check if a number is prime
@@ -35,52 +75,215 @@ pub fn is_prime(n: u32) -> bool {
     !(3..=sqrt).step_by(2).any(|i| n % i == 0)
 }
 
-/// Fibonacci calculation (recursive with memoization would be even faster)
+/// Fibonacci calculation
+/// Returns `None` (mapped to `null` in JS) if the result would overflow a `u64`,
+/// which happens starting around n = 94
 #[napi]
-pub fn fibonacci(n: u32) -> u64 {
+pub fn fibonacci(n: u32) -> Option<u64> {
     if n <= 1 {
-        return n as u64;
+        return Some(n as u64);
     }
     let mut a: u64 = 0;
     let mut b: u64 = 1;
     for _ in 2..=n {
-        let temp = a + b;
+        let temp = a.checked_add(b)?;
         a = b;
         b = temp;
     }
-    b
+    Some(b)
+}
+
+/// Arbitrary-precision Fibonacci for n beyond what fits in a u64
+#[napi]
+pub fn fibonacci_big(n: u32) -> BigInt {
+    let mut a = BigUint::from(0u32);
+    let mut b = BigUint::from(1u32);
+    for _ in 0..n {
+        let temp = &a + &b;
+        a = b;
+        b = temp;
+    }
+    BigInt::new(false, a.to_u64_digits())
+}
+
+/// Process-lifetime Fibonacci memoization table, indexed by `n`
+static FIBONACCI_CACHE: OnceLock<Mutex<Vec<BigUint>>> = OnceLock::new();
+
+fn fibonacci_cache() -> &'static Mutex<Vec<BigUint>> {
+    FIBONACCI_CACHE.get_or_init(|| Mutex::new(vec![BigUint::from(0u32), BigUint::from(1u32)]))
+}
+
+/// Fibonacci lookup backed by a shared, growable memoization table, so repeated
+/// calls (even for different `n`) amortize to linear work overall instead of
+/// recomputing from scratch each time
+#[napi]
+pub fn fibonacci_memoized(n: u32) -> BigInt {
+    let n = n as usize;
+    let mut table = fibonacci_cache().lock().unwrap();
+    while table.len() <= n {
+        let next = &table[table.len() - 1] + &table[table.len() - 2];
+        table.push(next);
+    }
+    BigInt::new(false, table[n].to_u64_digits())
+}
+
+/// Reset the Fibonacci memoization table, freeing its memory
+#[napi]
+pub fn clear_fibonacci_cache() {
+    *fibonacci_cache().lock().unwrap() = vec![BigUint::from(0u32), BigUint::from(1u32)];
 }
 
 /// Hash a password using a simple (demo) algorithm
-/// In production, use argon2 or bcrypt crate
+/// This is NOT suitable for real auth; use `hash_password_argon2` instead
 #[napi]
-pub fn hash_password(password: String, iterations: u32) -> String {
+pub fn hash_password_demo(password: String, iterations: u32) -> String {
     let mut hash: u64 = 0;
     let bytes = password.as_bytes();
-    
+
     for _ in 0..iterations {
         for (i, &byte) in bytes.iter().enumerate() {
             hash = hash.wrapping_mul(31).wrapping_add(byte as u64).wrapping_add(i as u64);
         }
     }
-    
+
     format!("{:016x}", hash)
 }
 
+/// Hash a password with Argon2id, returning the standard PHC-format string
+/// (`$argon2id$v=19$m=...`) with a fresh random salt. Pair with `verify_password`.
+#[napi]
+pub fn hash_password_argon2(password: String) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("argon2 hashing failed")
+        .to_string()
+}
+
+/// Verify a password against a PHC-format Argon2 hash produced by `hash_password_argon2`
+#[napi]
+pub fn verify_password(password: String, phc_hash: String) -> bool {
+    let Ok(parsed_hash) = PasswordHash::new(&phc_hash) else {
+        return false;
+    };
+    Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .is_ok()
+}
+
 /// Process an array of numbers (demonstrates working with JS arrays)
 #[napi]
 pub fn sum_array(numbers: Vec<f64>) -> f64 {
     numbers.iter().sum()
 }
 
-/// Parallel prime counting using Rayon (uncomment and add rayon to Cargo.toml)
-/// This demonstrates multi-threaded Rust code
-// use rayon::prelude::*;
-// 
-// #[napi]
-// pub fn count_primes_parallel(max: u32) -> u32 {
-//     (2..=max).into_par_iter().filter(|&n| is_prime(n)).count() as u32
-// }
+/// Size of each segment in the parallel segmented sieve, in numbers covered
+/// (one `bool` flag per number, so this is also the per-segment byte footprint)
+const SEGMENT_SIZE: u32 = 32 * 1024;
+
+/// Parallel prime counting using a segmented Sieve of Eratosthenes
+/// Base primes up to `sqrt(max)` are sieved sequentially, then `(sqrt(max), max]`
+/// is split into fixed-size segments that Rayon sieves concurrently against the
+/// base primes, which keeps each thread's working set cache-resident
+#[napi]
+pub fn count_primes_parallel(max: u32) -> u32 {
+    if max < 2 {
+        return 0;
+    }
+
+    let sqrt = (max as f64).sqrt() as u32;
+    let base_primes = primes_up_to(sqrt);
+
+    if max <= sqrt {
+        return base_primes.len() as u32;
+    }
+
+    let segment_total: usize = ((sqrt + 1)..=max)
+        .step_by(SEGMENT_SIZE as usize)
+        .collect::<Vec<u32>>()
+        .par_iter()
+        .map(|&lo| {
+            let hi = (lo as u64 + SEGMENT_SIZE as u64 - 1).min(max as u64) as u32;
+            count_primes_in_segment(lo, hi, &base_primes)
+        })
+        .sum();
+
+    base_primes.len() as u32 + segment_total as u32
+}
+
+/// Sieve the inclusive range `[lo, hi]` against the given base primes and
+/// return the count of survivors (the primes in that range)
+fn count_primes_in_segment(lo: u32, hi: u32, base_primes: &[u32]) -> usize {
+    let size = (hi - lo + 1) as usize;
+    let mut is_prime = vec![true; size];
+
+    for &p in base_primes {
+        let p = p as u64;
+        let start = (p * p).max(((lo as u64 + p - 1) / p) * p);
+        let mut multiple = start;
+        while multiple <= hi as u64 {
+            is_prime[(multiple - lo as u64) as usize] = false;
+            multiple += p;
+        }
+    }
+
+    is_prime.iter().filter(|&&is_p| is_p).count()
+}
+
+/// Above this inclusive upper bound, stepping a base prime (at most `u16::MAX`)
+/// past `hi` or rounding `lo` up to its next multiple can exceed `u32::MAX`,
+/// so the sieve must widen to `u64` arithmetic
+const NARROW_RANGE_THRESHOLD: u64 = (u16::MAX as u64) * (u16::MAX as u64);
+
+/// Count the primes in the half-open range `[lo, hi)`
+/// Like `count_primes_parallel`, this shards the range into fixed-size segments
+/// sieved concurrently against the base primes, so callers can shard work and
+/// avoid recomputing from 2 each time; it also keeps the hot loop on the
+/// narrowest integer width that can't overflow for the given bound
+#[napi]
+pub fn count_primes_in_range(lo: u32, hi: u32) -> u32 {
+    let lo = lo.max(2);
+    if hi <= lo {
+        return 0;
+    }
+    let hi = hi - 1; // work with an inclusive bound internally
+
+    let sqrt = (hi as f64).sqrt() as u32;
+    let base_primes = primes_up_to(sqrt);
+    let narrow = hi as u64 <= NARROW_RANGE_THRESHOLD;
+
+    (lo..=hi)
+        .step_by(SEGMENT_SIZE as usize)
+        .collect::<Vec<u32>>()
+        .par_iter()
+        .map(|&seg_lo| {
+            let seg_hi = (seg_lo as u64 + SEGMENT_SIZE as u64 - 1).min(hi as u64) as u32;
+            if narrow {
+                count_primes_in_segment_u32(seg_lo, seg_hi, &base_primes)
+            } else {
+                count_primes_in_segment(seg_lo, seg_hi, &base_primes)
+            }
+        })
+        .sum::<usize>() as u32
+}
+
+/// Same sieve as `count_primes_in_segment`, but kept on `u32` arithmetic for
+/// ranges narrow enough that stepping through multiples can't overflow — the fast path
+fn count_primes_in_segment_u32(lo: u32, hi: u32, base_primes: &[u32]) -> usize {
+    let size = (hi - lo + 1) as usize;
+    let mut is_prime = vec![true; size];
+
+    for &p in base_primes {
+        let start = (p * p).max(((lo + p - 1) / p) * p);
+        let mut multiple = start;
+        while multiple <= hi {
+            is_prime[(multiple - lo) as usize] = false;
+            multiple += p;
+        }
+    }
+
+    is_prime.iter().filter(|&&is_p| is_p).count()
+}
 
 #[cfg(test)]
 mod tests {
@@ -92,6 +295,12 @@ mod tests {
         assert_eq!(count_primes(100), 25);
     }
 
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(10), vec![2, 3, 5, 7]);
+        assert_eq!(primes_up_to(1), Vec::<u32>::new());
+    }
+
     #[test]
     fn test_is_prime() {
         assert!(!is_prime(0));
@@ -104,10 +313,59 @@ mod tests {
 
     #[test]
     fn test_fibonacci() {
-        assert_eq!(fibonacci(0), 0);
-        assert_eq!(fibonacci(1), 1);
-        assert_eq!(fibonacci(10), 55);
-        assert_eq!(fibonacci(20), 6765);
+        assert_eq!(fibonacci(0), Some(0));
+        assert_eq!(fibonacci(1), Some(1));
+        assert_eq!(fibonacci(10), Some(55));
+        assert_eq!(fibonacci(20), Some(6765));
+    }
+
+    #[test]
+    fn test_fibonacci_overflow() {
+        assert!(fibonacci(93).is_some());
+        assert_eq!(fibonacci(94), None);
+    }
+
+    #[test]
+    fn test_fibonacci_big() {
+        assert_eq!(fibonacci_big(0).words, Vec::<u64>::new());
+        assert_eq!(fibonacci_big(10).words, vec![55]);
+        // fib(94) exceeds u64::MAX, so it spills into a second little-endian limb
+        let fib_94 = fibonacci(93).unwrap() as u128 + fibonacci(92).unwrap() as u128;
+        assert_eq!(
+            fibonacci_big(94).words,
+            vec![fib_94 as u64, (fib_94 >> 64) as u64]
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_memoized() {
+        clear_fibonacci_cache();
+        assert_eq!(fibonacci_memoized(10).words, vec![55]);
+        // a lookup for a smaller n after the table has grown should still work
+        assert_eq!(fibonacci_memoized(5).words, vec![5]);
+        clear_fibonacci_cache();
+    }
+
+    #[test]
+    fn test_count_primes_parallel() {
+        assert_eq!(count_primes_parallel(10), 4);
+        assert_eq!(count_primes_parallel(100), 25);
+        assert_eq!(count_primes_parallel(100_000), count_primes(100_000));
+    }
+
+    #[test]
+    fn test_argon2_roundtrip() {
+        let hash = hash_password_argon2("hunter2".to_string());
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("hunter2".to_string(), hash.clone()));
+        assert!(!verify_password("wrong".to_string(), hash));
+    }
+
+    #[test]
+    fn test_count_primes_in_range() {
+        assert_eq!(count_primes_in_range(0, 10), 4); // 2, 3, 5, 7
+        assert_eq!(count_primes_in_range(10, 20), 4); // 11, 13, 17, 19
+        assert_eq!(count_primes_in_range(0, 100), count_primes(99));
     }
 }
 